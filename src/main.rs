@@ -1,27 +1,59 @@
-use pdf2john::PdfHashExtractor;
+use pdf2john::{HashFormat, PdfHashExtractor};
+use std::io;
 use std::process;
 
 fn usage() {
-    eprintln!("Usage: pdf2john [-s|--show-filename] <pdf_files>...");
+    eprintln!(
+        "Usage: pdf2john [-s|--show-filename] [--format john|hashcat] [--verify <password>] <pdf_files>..."
+    );
     eprintln!();
     eprintln!("Extract password hashes from encrypted PDFs for John the Ripper");
+    eprintln!("Use - as a filename to read the PDF from stdin.");
     eprintln!();
     eprintln!("Options:");
-    eprintln!("  -s, --show-filename  Prefix output with the filename");
-    eprintln!("  -h, --help           Print this help message");
+    eprintln!("  -s, --show-filename    Prefix output with the filename");
+    eprintln!("  --format john|hashcat  Hash encoding to emit (default: john)");
+    eprintln!("  --verify <password>    Check a candidate password instead of printing a hash");
+    eprintln!("  -h, --help             Print this help message");
 }
 
 fn main() {
     let mut show_filename = false;
+    let mut verify_candidate = None;
+    let mut format = HashFormat::John;
     let mut pdf_files = Vec::new();
 
-    for arg in std::env::args().skip(1) {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
         match arg.as_str() {
             "-s" | "--show-filename" => show_filename = true,
+            "--verify" => {
+                verify_candidate = Some(args.next().unwrap_or_else(|| {
+                    eprintln!("--verify requires a password argument");
+                    usage();
+                    process::exit(1);
+                }));
+            }
+            "--format" => {
+                let value = args.next().unwrap_or_else(|| {
+                    eprintln!("--format requires an argument (john or hashcat)");
+                    usage();
+                    process::exit(1);
+                });
+                format = match value.as_str() {
+                    "john" => HashFormat::John,
+                    "hashcat" => HashFormat::Hashcat,
+                    other => {
+                        eprintln!("Unknown format: {other} (expected john or hashcat)");
+                        process::exit(1);
+                    }
+                };
+            }
             "-h" | "--help" => {
                 usage();
                 process::exit(0);
             }
+            "-" => pdf_files.push(arg),
             s if s.starts_with('-') => {
                 eprintln!("Unknown option: {s}");
                 eprintln!();
@@ -39,14 +71,43 @@ fn main() {
 
     let mut had_error = false;
 
+    let stdin = io::stdin();
+
     for filename in &pdf_files {
-        match PdfHashExtractor::from_file(filename) {
+        let result = if filename == "-" {
+            PdfHashExtractor::from_reader(stdin.lock())
+        } else {
+            PdfHashExtractor::from_file(filename)
+        };
+
+        match result {
             Ok(extractor) => {
-                let hash = extractor.format_hash();
-                if show_filename {
-                    println!("{filename}:{hash}");
-                } else {
-                    println!("{hash}");
+                if let Some(ref candidate) = verify_candidate {
+                    let matched = extractor.verify_password(candidate.as_bytes());
+                    let status = if matched { "match" } else { "no match" };
+                    if show_filename {
+                        println!("{filename}: {status}");
+                    } else {
+                        println!("{status}");
+                    }
+                    if !matched {
+                        had_error = true;
+                    }
+                    continue;
+                }
+
+                match extractor.format_hash_as(format) {
+                    Ok(hash) => {
+                        if show_filename {
+                            println!("{filename}:{hash}");
+                        } else {
+                            println!("{hash}");
+                        }
+                    }
+                    Err(e) => {
+                        eprintln!("{filename}: {e}");
+                        had_error = true;
+                    }
                 }
             }
             Err(e) => {